@@ -1,23 +1,27 @@
 use futures::stream::StreamExt;
 use futures::try_join;
-use serde::{Deserialize, Serialize};
-use tokio::io::{AsyncBufRead, AsyncWriteExt};
-use std::io::{BufRead, BufReader};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use std::io::SeekFrom;
 use std::net::{AddrParseError, SocketAddr, TcpListener};
 use std::path::Path;
-use std::process::{Child, Command, Stdio};
 use std::str::FromStr;
 use tokio::fs;
-use tokio::{
-    task,
-};
-use tauri::{AppHandle, Emitter, Listener, Manager};
-use std::sync::Arc;
-use tokio::sync::Mutex as AsyncMutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+mod logging;
+mod process;
+mod updates;
+use logging::ConsoleEvent;
+use process::{ProcessKind, ProcessRegistry, ProcessStatus};
 
 #[derive(serde::Deserialize)]
 struct GitHubRelease {
     tag_name: String,
+    prerelease: bool,
+    body: Option<String>,
+    published_at: String,
     assets: Vec<GitHubAsset>,
 }
 
@@ -27,6 +31,79 @@ struct GitHubAsset {
     browser_download_url: String,
 }
 
+/// One download target plus the digest we expect it to hash to, if the
+/// release published a sidecar `.sha256` asset for it.
+#[derive(Serialize, Clone)]
+struct AssetInfo {
+    url: String,
+    sha256: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+struct UpdateAvailable {
+    tag: String,
+    channel: String,
+    changelog: String,
+    published_at: String,
+    client: AssetInfo,
+    server: AssetInfo,
+}
+
+/// Emitted to the frontend as download bytes arrive so it can render a progress bar.
+#[derive(Serialize, Clone)]
+struct DownloadProgress {
+    file: String,
+    downloaded: u64,
+    total: u64,
+    percent: f64,
+}
+
+/// Fetches the sidecar digest asset for `asset_name` (e.g. `client-v1.2.3.tar.gz` ->
+/// `client-v1.2.3.tar.gz.sha256`), if the release published one.
+async fn fetch_expected_digest(
+    client: &reqwest::Client,
+    assets: &[GitHubAsset],
+    asset_name: &str,
+) -> Option<String> {
+    let digest_name = format!("{}.sha256", asset_name);
+    let digest_asset = assets.iter().find(|a| a.name == digest_name)?;
+
+    let response = client
+        .get(&digest_asset.browser_download_url)
+        .header("User-Agent", "ClashRoyale/v1")
+        .send()
+        .await
+        .ok()?;
+
+    let body = response.text().await.ok()?;
+    // Digest files are typically "<hex>  <filename>" or just "<hex>".
+    body.split_whitespace().next().map(|hex| hex.to_lowercase())
+}
+
+/// Parses a tag like `v1.2.3` or `1.2.3` as a semver version, so releases can be compared
+/// by actual version ordering instead of by tag string inequality.
+fn parse_tag_version(tag: &str) -> Option<semver::Version> {
+    semver::Version::parse(tag.trim_start_matches('v')).ok()
+}
+
+/// Finds the asset for `kind` (`"client"` or `"server"`) built for the platform we're
+/// running on, e.g. `client-v1.2.3-linux-x86_64.tar.gz`, falling back to a suffix-less
+/// `client-v1.2.3.tar.gz` for releases that don't publish per-platform archives.
+fn find_platform_asset<'a>(
+    assets: &'a [GitHubAsset],
+    kind: &str,
+    tag: &str,
+) -> Option<&'a GitHubAsset> {
+    let prefix = format!("{}-", kind);
+    let platform_suffix = format!("-{}-{}.tar.gz", std::env::consts::OS, std::env::consts::ARCH);
+    let suffixless_name = format!("{}-{}.tar.gz", kind, tag);
+
+    assets
+        .iter()
+        .find(|a| a.name.starts_with(&prefix) && a.name.ends_with(&platform_suffix))
+        .or_else(|| assets.iter().find(|a| a.name == suffixless_name))
+}
+
 fn kill_process_using_port(port: u16) -> Result<Option<String>, String> {
     let addr: SocketAddr = format!("127.0.0.1:{}", port).parse().map_err(|e: AddrParseError| e.to_string())?;
     
@@ -92,14 +169,25 @@ fn kill_process_using_port(port: u16) -> Result<Option<String>, String> {
 
 
 
+/// `stable` picks the newest non-prerelease; `beta` picks the newest prerelease. Anything
+/// else is rejected rather than silently falling back to `stable`.
+fn wants_prerelease(channel: &str) -> Result<bool, String> {
+    match channel {
+        "stable" => Ok(false),
+        "beta" => Ok(true),
+        other => Err(format!("Unknown release channel: {}", other)),
+    }
+}
+
 #[tauri::command]
-async fn check_for_updates(current_tag: String) -> Result<Option<(String, String)>, String> {
+async fn check_for_updates(current_tag: String, channel: String) -> Result<Option<UpdateAvailable>, String> {
     let repo_owner = "Superbro525Alt";
     let repo_name = "Y11PY";
     let api_url = format!(
-        "https://api.github.com/repos/{}/{}/releases/latest",
+        "https://api.github.com/repos/{}/{}/releases",
         repo_owner, repo_name
     );
+    let want_prerelease = wants_prerelease(&channel)?;
 
     let client = reqwest::Client::new();
     let response = match client
@@ -109,7 +197,7 @@ async fn check_for_updates(current_tag: String) -> Result<Option<(String, String
         .await
     {
         Ok(res) => res,
-        Err(e) => return Err(format!("Failed to fetch latest release info: {}", e)),
+        Err(e) => return Err(format!("Failed to fetch release list: {}", e)),
     };
 
     if !response.status().is_success() {
@@ -119,134 +207,297 @@ async fn check_for_updates(current_tag: String) -> Result<Option<(String, String
         ));
     }
 
-    let release_info: GitHubRelease = match response.json().await {
+    let releases: Vec<GitHubRelease> = match response.json().await {
         Ok(data) => data,
-        Err(e) => return Err(format!("Failed to parse latest release info: {}", e)),
+        Err(e) => return Err(format!("Failed to parse release list: {}", e)),
     };
 
-    if release_info.tag_name != current_tag {
-        let mut client_url = None;
-        let mut server_url = None;
+    let current_version = parse_tag_version(&current_tag);
 
-        for asset in &release_info.assets {
-            if asset.name.starts_with("client-") && asset.name.ends_with(".tar.gz") {
-                client_url = Some(asset.browser_download_url.clone());
-            } else if asset.name.starts_with("server-") && asset.name.ends_with(".tar.gz") {
-                server_url = Some(asset.browser_download_url.clone());
-            }
-        }
+    let newest = releases
+        .into_iter()
+        .filter(|r| r.prerelease == want_prerelease)
+        .filter_map(|r| parse_tag_version(&r.tag_name).map(|v| (v, r)))
+        .max_by(|(a, _), (b, _)| a.cmp(b));
 
-        match (client_url, server_url) {
-            (Some(client), Some(server)) => Ok(Some((client, server))),
-            _ => Ok(Some(("Could not find both client and server packages.".into(), "".into()))), 
+    let Some((version, release_info)) = newest else {
+        return Ok(None);
+    };
+
+    let is_newer = match &current_version {
+        Some(current) => version > *current,
+        None => true,
+    };
+    if !is_newer {
+        return Ok(None);
+    }
+
+    let client_asset = find_platform_asset(&release_info.assets, "client", &release_info.tag_name);
+    let server_asset = find_platform_asset(&release_info.assets, "server", &release_info.tag_name);
+
+    match (client_asset, server_asset) {
+        (Some(client_asset), Some(server_asset)) => {
+            let client_sha256 =
+                fetch_expected_digest(&client, &release_info.assets, &client_asset.name).await;
+            let server_sha256 =
+                fetch_expected_digest(&client, &release_info.assets, &server_asset.name).await;
+
+            Ok(Some(UpdateAvailable {
+                tag: release_info.tag_name.clone(),
+                channel,
+                changelog: release_info.body.clone().unwrap_or_default(),
+                published_at: release_info.published_at.clone(),
+                client: AssetInfo {
+                    url: client_asset.browser_download_url.clone(),
+                    sha256: client_sha256,
+                },
+                server: AssetInfo {
+                    url: server_asset.browser_download_url.clone(),
+                    sha256: server_sha256,
+                },
+            }))
         }
-    } else {
-        Ok(None)
+        _ => Err(format!(
+            "Could not find both client and server packages for {}-{}.",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )),
     }
 }
 
-#[tauri::command]
-async fn download_and_extract_updates(client_url: String, server_url: String) -> Result<(), String> {
-    let download_dir = Path::new("updates");
-    fs::create_dir_all(&download_dir)
-        .await
-        .map_err(|e| format!("Failed to create download directory: {}", e))?;
-
-    async fn download_file(url: String, filename: &Path) -> Result<(), String> {
-        let client = reqwest::Client::new();
-        let response = match client.get(&url).send().await {
-            Ok(res) => res,
-            Err(e) => return Err(format!("Failed to download {}: {}", filename.display(), e)),
-        };
-
-        if !response.status().is_success() {
+/// Finalizes `hasher` and compares it against `expected` (when the release published a
+/// digest), returning a descriptive error on mismatch.
+fn verify_checksum(hasher: Sha256, file_label: &str, expected: Option<&str>) -> Result<(), String> {
+    if let Some(expected) = expected {
+        let actual = hex::encode(hasher.finalize());
+        if !actual.eq_ignore_ascii_case(expected) {
             return Err(format!(
-                "Failed to download {}, status: {}",
-                filename.display(),
-                response.status()
+                "Checksum mismatch for {}: expected {}, got {}",
+                file_label, expected, actual
             ));
         }
+    }
+    Ok(())
+}
 
-        let mut stream = response.bytes_stream();
-        let mut file = fs::File::create(filename)
+/// Downloads `url` into `filename`, resuming a previous partial download when possible,
+/// emitting `download-progress` events as bytes arrive, and verifying the result against
+/// `expected_sha256` (when the release published one) before returning.
+async fn download_file(
+    app_handle: &AppHandle,
+    url: String,
+    filename: &Path,
+    expected_sha256: Option<&str>,
+) -> Result<(), String> {
+    let file_label = filename
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| filename.display().to_string());
+
+    let client = reqwest::Client::new();
+
+    let mut hasher = Sha256::new();
+    let mut already_downloaded: u64 = 0;
+    let mut file = if let Ok(metadata) = fs::metadata(filename).await {
+        already_downloaded = metadata.len();
+        let mut existing = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(filename)
             .await
-            .map_err(|e| format!("Failed to create file {}: {}", filename.display(), e))?;
+            .map_err(|e| format!("Failed to reopen partial file {}: {}", filename.display(), e))?;
 
-        while let Some(chunk) = stream.next().await {
-            let bytes = chunk.map_err(|e| format!("Error reading download stream for {}: {}", filename.display(), e))?;
-            file.write_all(&bytes)
+        // Fold the bytes already on disk into the running digest before appending more.
+        existing
+            .seek(SeekFrom::Start(0))
+            .await
+            .map_err(|e| format!("Failed to seek {}: {}", filename.display(), e))?;
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = existing
+                .read(&mut buf)
                 .await
-                .map_err(|e| format!("Error writing to file {}: {}", filename.display(), e))?;
+                .map_err(|e| format!("Failed to read {}: {}", filename.display(), e))?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
         }
+        existing
+    } else {
+        already_downloaded = 0;
+        fs::File::create(filename)
+            .await
+            .map_err(|e| format!("Failed to create file {}: {}", filename.display(), e))?
+    };
+
+    let mut request = client.get(&url);
+    if already_downloaded > 0 {
+        request = request.header("Range", format!("bytes={}-", already_downloaded));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", file_label, e))?;
+
+    // A 416 in response to our `Range: bytes=<len>-` means the server considers the file
+    // we already have to be the whole thing (a previous run's last chunk landed on disk but
+    // the command got interrupted before verification). There's nothing left to stream;
+    // fall straight through to checksum verification against what's already on disk.
+    if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        return verify_checksum(hasher, &file_label, expected_sha256);
+    }
 
-        Ok(())
+    let resuming = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to download {}, status: {}",
+            file_label,
+            response.status()
+        ));
+    }
+
+    // A server that ignores Range and sends 200 OK would have us append onto a
+    // complete file; start over from scratch in that case.
+    if already_downloaded > 0 && !resuming {
+        already_downloaded = 0;
+        hasher = Sha256::new();
+        file = fs::File::create(filename)
+            .await
+            .map_err(|e| format!("Failed to recreate file {}: {}", filename.display(), e))?;
     }
 
-    let client_file_path = download_dir.join("client.tar.gz");
-    let server_file_path = download_dir.join("server.tar.gz");
+    let total = response
+        .content_length()
+        .map(|len| len + already_downloaded)
+        .unwrap_or(already_downloaded);
+
+    let mut downloaded = already_downloaded;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let bytes = chunk.map_err(|e| format!("Error reading download stream for {}: {}", file_label, e))?;
+        hasher.update(&bytes);
+        file.write_all(&bytes)
+            .await
+            .map_err(|e| format!("Error writing to file {}: {}", file_label, e))?;
+
+        downloaded += bytes.len() as u64;
+        let percent = if total > 0 {
+            (downloaded as f64 / total as f64) * 100.0
+        } else {
+            0.0
+        };
+        let _ = app_handle.emit(
+            "download-progress",
+            DownloadProgress {
+                file: file_label.clone(),
+                downloaded,
+                total,
+                percent,
+            },
+        );
+    }
+
+    verify_checksum(hasher, &file_label, expected_sha256)
+}
+
+async fn extract_tar_gz(archive_path: &Path, extract_path: &Path) -> Result<(), String> {
+    let tar_gz = fs::File::open(archive_path)
+        .await
+        .map_err(|e| format!("Failed to open archive {}: {}", archive_path.display(), e))?;
+    let tar = flate2::read::GzDecoder::new(tar_gz.into_std().await);
+    let mut archive = tar::Archive::new(tar);
+    archive
+        .unpack(extract_path)
+        .map_err(|e| format!("Failed to extract {} to {}: {}", archive_path.display(), extract_path.display(), e))?;
+    Ok(())
+}
+
+/// Downloads and verifies the client/server archives for `tag` into a scratch
+/// `updates/staging/<tag>` directory, extracts them there, and only once both have
+/// succeeded atomically swaps the staged directories into `client_update`/`server_update`.
+/// A failed download or extraction never touches the live install; a failed swap (or a
+/// build that doesn't launch afterwards) can be undone with `rollback_update`.
+#[tauri::command]
+async fn download_and_extract_updates(
+    app_handle: AppHandle,
+    tag: String,
+    client_url: String,
+    server_url: String,
+    client_sha256: Option<String>,
+    server_sha256: Option<String>,
+) -> Result<(), String> {
+    let staging_dir = Path::new("updates/staging").join(&tag);
+    fs::create_dir_all(&staging_dir)
+        .await
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+
+    let client_file_path = staging_dir.join("client.tar.gz");
+    let server_file_path = staging_dir.join("server.tar.gz");
 
     try_join!(
-        download_file(client_url, &client_file_path),
-        download_file(server_url, &server_file_path),
+        download_file(&app_handle, client_url, &client_file_path, client_sha256.as_deref()),
+        download_file(&app_handle, server_url, &server_file_path, server_sha256.as_deref()),
     )
     .map_err(|e| format!("Failed to download one or both files: {}", e))?;
 
-    // Extraction
-    let client_extract_dir = Path::new("client_update");
+    let client_extract_dir = staging_dir.join("client");
+    let server_extract_dir = staging_dir.join("server");
     fs::create_dir_all(&client_extract_dir)
         .await
         .map_err(|e| format!("Failed to create client extract directory: {}", e))?;
-
-    let server_extract_dir = Path::new("server_update");
     fs::create_dir_all(&server_extract_dir)
         .await
         .map_err(|e| format!("Failed to create server extract directory: {}", e))?;
 
-    async fn extract_tar_gz(archive_path: &Path, extract_path: &Path) -> Result<(), String> {
-        let tar_gz = fs::File::open(archive_path)
-            .await
-            .map_err(|e| format!("Failed to open archive {}: {}", archive_path.display(), e))?;
-        let tar = flate2::read::GzDecoder::new(tar_gz.into_std().await);
-        let mut archive = tar::Archive::new(tar);
-        archive
-            .unpack(extract_path)
-            .map_err(|e| format!("Failed to extract {} to {}: {}", archive_path.display(), extract_path.display(), e))?;
-        Ok(())
-    }
-
     try_join!(
         extract_tar_gz(&client_file_path, &client_extract_dir),
         extract_tar_gz(&server_file_path, &server_extract_dir),
     )
     .map_err(|e| format!("Failed to extract one or both archives: {}", e))?;
 
+    updates::apply_staged(&tag, &client_extract_dir, &server_extract_dir).await?;
+
+    fs::remove_dir_all(&staging_dir).await.ok();
+
     Ok(())
 }
 
 #[tauri::command]
-fn start_game(app_handle: AppHandle, name: String, ip: String) -> Result<(), String> {
+async fn rollback_update() -> Result<(), String> {
+    updates::rollback().await
+}
+
+#[tauri::command]
+async fn start_game(
+    app_handle: AppHandle,
+    registry: State<'_, ProcessRegistry>,
+    name: String,
+    ip: String,
+) -> Result<(), String> {
     let game_path = Path::new("./client_update/client.dist").join("client.bin");
-    
+
     if !game_path.exists() {
         return Err("Game executable not found".into());
     }
 
-    let mut cmd = Command::new(game_path);
+    let mut cmd = tokio::process::Command::new(game_path);
     cmd.arg("--name").arg(name);
     cmd.arg("--ip").arg(ip);
 
-    match cmd.spawn() {
-        Ok(child) => {
-            app_handle.emit("game-process", child.id()).unwrap();
-            Ok(())
-        },
-        Err(e) => Err(format!("Failed to launch game: {}", e)),
-    }
+    let pid = registry.spawn(app_handle.clone(), ProcessKind::Game, cmd).await?;
+    let _ = app_handle.emit("game-process", pid);
+    Ok(())
 }
 
 #[tauri::command]
-async fn start_server(app_handle: AppHandle) -> Result<(), String> {
-    kill_process_using_port(12345).unwrap();
+async fn start_server(
+    app_handle: AppHandle,
+    registry: State<'_, ProcessRegistry>,
+) -> Result<(), String> {
+    kill_process_using_port(12345)?;
 
     let game_path = Path::new("./server_update").join("server.bin");
 
@@ -254,151 +505,87 @@ async fn start_server(app_handle: AppHandle) -> Result<(), String> {
         return Err("Server executable not found".into());
     }
 
-    let mut cmd = Command::new(game_path);
-    cmd.stdout(Stdio::piped());
-    cmd.stderr(Stdio::piped());
-    let handle_stdout = app_handle.clone();
-    let handle_stderr = app_handle.clone();
-
-    match cmd.spawn() {
-        Ok(mut child) => {
-            let pid = child.id();
-            app_handle.emit("server-process", pid).unwrap();
-
-            let stdout_option = child.stdout.take();
-            let stderr_option = child.stderr.take();
-
-            if let Some(stdout) = stdout_option {
-                task::spawn(async move {
-                    let reader = BufReader::new(stdout);
-                    let mut lines = reader.lines().fuse();
-
-                    while let Some(result) = lines.next() {
-                        match result {
-                            Ok(line) => {
-                                handle_stdout.emit("server-log", line).unwrap();
-                            }
-                            Err(e) => {
-                                eprintln!("Error reading server stdout: {}", e);
-                                break;
-                            }
-                        }
-                    }
-                });
-            }
+    let cmd = tokio::process::Command::new(game_path);
+    let pid = registry.spawn(app_handle.clone(), ProcessKind::Server, cmd).await?;
+    let _ = app_handle.emit("server-process", pid);
+    Ok(())
+}
 
-            if let Some(stderr) = stderr_option {
-                task::spawn(async move {
-                    let reader = BufReader::new(stderr);
-                    let mut lines = reader.lines().fuse();
-                    while let Some(result) = lines.next() {
-                        match result {
-                            Ok(line) => {
-                                handle_stderr.emit("server-error", line).unwrap();
-                            }
-                            Err(e) => {
-                                eprintln!("Error reading server stderr: {}", e);
-                                break;
-                            }
-                        }
-                    }
-                });
-            }
+/// Spawns an arbitrary program under `id`, streaming its stdout/stderr as
+/// `proc://<id>/stdout` and `proc://<id>/stderr` events and reporting `proc://<id>/exit`
+/// when it terminates. Pair with `write_stdin` to drive an interactive process (e.g. issue
+/// commands to the server console) without adding another dedicated launcher command.
+#[tauri::command]
+async fn spawn(
+    app_handle: AppHandle,
+    registry: State<'_, ProcessRegistry>,
+    id: String,
+    program: String,
+    args: Vec<String>,
+) -> Result<u32, String> {
+    let mut cmd = tokio::process::Command::new(program);
+    cmd.args(args);
+    registry.spawn(app_handle, ProcessKind::from_id(&id), cmd).await
+}
 
-            Ok(())
-        }
-        Err(e) => Err(format!("Failed to start server: {}", e)),
-    }
+#[tauri::command]
+async fn write_stdin(
+    registry: State<'_, ProcessRegistry>,
+    id: String,
+    line: String,
+) -> Result<(), String> {
+    registry.write_stdin(&ProcessKind::from_id(&id), &line).await
 }
 
 #[tauri::command]
-async fn stop_server(app_handle: AppHandle) -> Result<(), String> {
-    let result = app_handle.emit("stop-server", ());
-    match result {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Failed to stop server: {}", e)),
-    }
+async fn stop_server(registry: State<'_, ProcessRegistry>) -> Result<(), String> {
+    registry.stop(ProcessKind::Server).await
 }
 
 #[tauri::command]
-async fn stop_game(app_handle: AppHandle) -> Result<(), String> {
-    let result = app_handle.emit("stop-game", ());
-    match result {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Failed to stop game: {}", e)),
-    }
+async fn stop_game(registry: State<'_, ProcessRegistry>) -> Result<(), String> {
+    registry.stop(ProcessKind::Game).await
 }
 
 #[tauri::command]
-fn get_current_version(app_handle: AppHandle) -> Result<(), String> {
-    let file_content = std::fs::read_to_string("config.json")
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+async fn process_status(registry: State<'_, ProcessRegistry>) -> Result<ProcessStatus, String> {
+    Ok(registry.status().await)
+}
 
-    let config: Config = serde_json::from_str(&file_content)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+#[tauri::command]
+fn get_log_history(limit: usize) -> Vec<ConsoleEvent> {
+    logging::history(limit)
+}
 
+#[tauri::command]
+fn get_current_version(app_handle: AppHandle) -> Result<(), String> {
+    let config = updates::read_config()?;
     app_handle.emit("config-current-version", config.current_version).unwrap();
-
     Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
-struct Config {
-    current_version: String
-}
 pub fn run() {
+    logging::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(ProcessRegistry::new())
         .setup(|app| {
-
-            // Setup event listeners for stopping processes
-
-            let app_handle = app.handle();
-
-            app.listen("stop-server", move |_| {
-                #[cfg(not(windows))]
-                std::process::Command::new("pkill")
-                    .arg("-f")
-                    .arg("server.bin")
-                    .spawn()
-                    .ok();
-
-                #[cfg(windows)]
-                std::process::Command::new("taskkill")
-                    .arg("/F")
-                    .arg("/IM")
-                    .arg("server.bin")
-                    .spawn()
-                    .ok();
-            });
-
-            let app_handle_game = app_handle.clone();
-            app.listen("stop-game", move |_| {
-                #[cfg(not(windows))]
-                std::process::Command::new("pkill")
-                    .arg("-f")
-                    .arg("client.bin")
-                    .spawn()
-                    .ok();
-
-                #[cfg(windows)]
-                std::process::Command::new("taskkill")
-                    .arg("/F")
-                    .arg("/IM")
-                    .arg("client.bin")
-                    .spawn()
-                    .ok();
-            });
-
+            logging::set_app_handle(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
-            download_and_extract_updates, 
-            check_for_updates, 
-            start_game, 
-            start_server, 
-            stop_server, 
+            download_and_extract_updates,
+            rollback_update,
+            check_for_updates,
+            start_game,
+            start_server,
+            stop_server,
             stop_game,
+            process_status,
+            spawn,
+            write_stdin,
+            get_log_history,
             get_current_version
         ])
         .run(tauri::generate_context!())
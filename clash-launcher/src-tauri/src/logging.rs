@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{Log, Metadata, Record};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+const MAX_HISTORY: usize = 500;
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const LOG_FILE_NAME: &str = "launcher.log";
+
+/// A log record shaped for the frontend console view.
+#[derive(Serialize, Clone)]
+pub struct ConsoleEvent {
+    pub level: String,
+    pub target: String,
+    pub timestamp: u128,
+    pub message: String,
+}
+
+/// A plain append-only file that starts over once it grows past `MAX_LOG_BYTES`,
+/// keeping one rotated copy around for postmortems.
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.file.metadata().map(|m| m.len()).unwrap_or(0) > MAX_LOG_BYTES {
+            let rotated = self.path.with_extension("log.1");
+            let _ = fs::rename(&self.path, &rotated);
+            if let Ok(file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+                self.file = file;
+            }
+        }
+        let _ = writeln!(self.file, "{}", line);
+    }
+}
+
+/// Implements `log::Log` so every `log::info!`/`log::error!` call in the crate is both
+/// buffered for `get_log_history` and forwarded to the main window's console, in addition
+/// to being written to disk. The `AppHandle` isn't available until the Tauri app is built,
+/// so it's filled in later via [`LogForwarder::set_app_handle`].
+struct LogForwarder {
+    app_handle: OnceLock<AppHandle>,
+    history: Mutex<VecDeque<ConsoleEvent>>,
+    file: Mutex<RotatingFile>,
+}
+
+impl LogForwarder {
+    fn new(log_path: PathBuf) -> std::io::Result<Self> {
+        Ok(Self {
+            app_handle: OnceLock::new(),
+            history: Mutex::new(VecDeque::with_capacity(MAX_HISTORY)),
+            file: Mutex::new(RotatingFile::open(log_path)?),
+        })
+    }
+
+    fn set_app_handle(&self, app_handle: AppHandle) {
+        let _ = self.app_handle.set(app_handle);
+    }
+
+    fn history(&self, limit: usize) -> Vec<ConsoleEvent> {
+        let history = self.history.lock().unwrap();
+        let skip = history.len().saturating_sub(limit);
+        history.iter().skip(skip).cloned().collect()
+    }
+}
+
+impl Log for LogForwarder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+
+        let event = ConsoleEvent {
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            timestamp,
+            message: format!("{}", record.args()),
+        };
+
+        {
+            let mut history = self.history.lock().unwrap();
+            if history.len() >= MAX_HISTORY {
+                history.pop_front();
+            }
+            history.push_back(event.clone());
+        }
+
+        if let Ok(mut file) = self.file.lock() {
+            file.write_line(&format!(
+                "[{}] {} {} - {}",
+                event.timestamp, event.level, event.target, event.message
+            ));
+        }
+
+        if let Some(app_handle) = self.app_handle.get() {
+            let _ = app_handle.emit("console-log", event);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.file.flush();
+        }
+    }
+}
+
+static FORWARDER: OnceLock<LogForwarder> = OnceLock::new();
+
+/// Installs the launcher's `log::Log` implementation. Must run before anything else
+/// (including building the Tauri app) calls into the `log` macros.
+pub fn init() {
+    let forwarder =
+        LogForwarder::new(PathBuf::from(LOG_FILE_NAME)).expect("failed to open launcher log file");
+
+    if FORWARDER.set(forwarder).is_err() {
+        return;
+    }
+
+    log::set_logger(FORWARDER.get().unwrap())
+        .map(|()| log::set_max_level(log::LevelFilter::Info))
+        .expect("logger already initialized");
+}
+
+/// Hands the forwarder the `AppHandle` it needs to reach the frontend console, once the
+/// Tauri app has finished building.
+pub fn set_app_handle(app_handle: AppHandle) {
+    if let Some(forwarder) = FORWARDER.get() {
+        forwarder.set_app_handle(app_handle);
+    }
+}
+
+/// Replays the last `limit` buffered log lines, for a freshly opened console view.
+pub fn history(limit: usize) -> Vec<ConsoleEvent> {
+    FORWARDER.get().map(|f| f.history(limit)).unwrap_or_default()
+}
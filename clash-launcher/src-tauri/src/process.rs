@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::process::ExitStatus;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::Mutex as AsyncMutex;
+
+/// Identifies a tracked process. `Game`/`Server` back the dedicated `start_game`/
+/// `start_server` commands; `Custom` backs the generic `spawn` command, keyed by whatever
+/// logical id the caller chose.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProcessKind {
+    Game,
+    Server,
+    Custom(String),
+}
+
+impl ProcessKind {
+    /// The id used in `proc://<id>/...` event topics and error messages.
+    pub fn id(&self) -> String {
+        match self {
+            ProcessKind::Game => "game".to_string(),
+            ProcessKind::Server => "server".to_string(),
+            ProcessKind::Custom(id) => id.clone(),
+        }
+    }
+
+    /// Maps an id from the frontend back to a kind, so `write_stdin`/`spawn` can target
+    /// the dedicated game/server processes as well as ones spawned generically.
+    pub fn from_id(id: &str) -> ProcessKind {
+        match id {
+            "game" => ProcessKind::Game,
+            "server" => ProcessKind::Server,
+            other => ProcessKind::Custom(other.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct ProcessExited {
+    pid: u32,
+    code: Option<i32>,
+    #[cfg(unix)]
+    signal: Option<i32>,
+}
+
+#[derive(Serialize, Clone, Copy, Default)]
+pub struct ProcessStatus {
+    pub game: bool,
+    pub server: bool,
+}
+
+struct TrackedProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+}
+
+fn exit_event(pid: u32, status: ExitStatus) -> ProcessExited {
+    ProcessExited {
+        pid,
+        code: status.code(),
+        #[cfg(unix)]
+        signal: {
+            use std::os::unix::process::ExitStatusExt;
+            status.signal()
+        },
+    }
+}
+
+/// Tracks the exact child handles we spawned, so stop/restart/stdin act on the process we
+/// launched instead of falling back to killing anything that matches an executable name.
+#[derive(Default)]
+pub struct ProcessRegistry(Arc<AsyncMutex<HashMap<ProcessKind, TrackedProcess>>>);
+
+impl ProcessRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns `cmd` with piped stdio and tracks it under `kind`, killing whatever was
+    /// previously tracked there. Stdout/stderr are read line-by-line on async handles and
+    /// fanned out to both the log forwarder (`target = <id>`, so it shows up in the
+    /// regular console/log history) and `proc://<id>/stdout`/`proc://<id>/stderr` events
+    /// for listeners scoped to just this process. `proc://<id>/exit` fires once, when the
+    /// watcher (polling every 300ms) sees the process exit on its own; no exit event is sent
+    /// for a process removed by `stop`, replaced by a later `spawn` of the same kind, or
+    /// reaped first by `status`.
+    pub async fn spawn(
+        &self,
+        app_handle: AppHandle,
+        kind: ProcessKind,
+        mut cmd: Command,
+    ) -> Result<u32, String> {
+        cmd.stdin(std::process::Stdio::piped());
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
+
+        let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn process: {}", e))?;
+        let pid = child.id().ok_or("Process exited before it could be tracked")?;
+        let id = kind.id();
+
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        if let Some(stdout) = stdout {
+            let app_handle = app_handle.clone();
+            let id = id.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    log::info!(target: id.as_str(), "{}", line);
+                    let _ = app_handle.emit(&format!("proc://{}/stdout", id), line);
+                }
+            });
+        }
+
+        if let Some(stderr) = stderr {
+            let app_handle = app_handle.clone();
+            let id = id.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    log::error!(target: id.as_str(), "{}", line);
+                    let _ = app_handle.emit(&format!("proc://{}/stderr", id), line);
+                }
+            });
+        }
+
+        let previous = {
+            let mut processes = self.0.lock().await;
+            processes.insert(kind.clone(), TrackedProcess { child, stdin })
+        };
+        if let Some(mut previous) = previous {
+            let _ = previous.child.start_kill();
+        }
+
+        let processes = self.0.clone();
+        let watch_kind = kind.clone();
+        let watch_id = id.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+
+                let mut processes = processes.lock().await;
+                let Some(tracked) = processes.get_mut(&watch_kind) else {
+                    // Stopped explicitly elsewhere; nothing left to watch.
+                    break;
+                };
+
+                // The entry under `watch_kind` might belong to a newer `spawn()` call for
+                // the same kind by now (a restart replaces the map entry but doesn't stop
+                // the old watcher). If the tracked pid no longer matches the one we were
+                // launched to watch, it's not our process any more, so stop silently
+                // instead of reaping or reporting the new process's exit under our pid.
+                if tracked.child.id() != Some(pid) {
+                    break;
+                }
+
+                match tracked.child.try_wait() {
+                    Ok(None) => continue,
+                    Ok(Some(status)) => {
+                        processes.remove(&watch_kind);
+                        drop(processes);
+                        let _ = app_handle.emit(
+                            &format!("proc://{}/exit", watch_id),
+                            exit_event(pid, status),
+                        );
+                        break;
+                    }
+                    Err(_) => {
+                        processes.remove(&watch_kind);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(pid)
+    }
+
+    /// Writes `line` (plus a trailing newline) to the tracked process's stdin, e.g. to
+    /// issue a command into a running server console.
+    pub async fn write_stdin(&self, kind: &ProcessKind, line: &str) -> Result<(), String> {
+        let mut processes = self.0.lock().await;
+        let tracked = processes
+            .get_mut(kind)
+            .ok_or_else(|| format!("No tracked process for {}", kind.id()))?;
+        let stdin = tracked
+            .stdin
+            .as_mut()
+            .ok_or_else(|| format!("{} has no stdin to write to", kind.id()))?;
+
+        stdin
+            .write_all(format!("{}\n", line).as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to {} stdin: {}", kind.id(), e))
+    }
+
+    /// Kills and forgets the tracked process for `kind`, if one is running.
+    pub async fn stop(&self, kind: ProcessKind) -> Result<(), String> {
+        let mut processes = self.0.lock().await;
+        if let Some(mut tracked) = processes.remove(&kind) {
+            tracked
+                .child
+                .start_kill()
+                .map_err(|e| format!("Failed to kill {}: {}", kind.id(), e))?;
+            let _ = tracked.child.wait().await;
+        }
+        Ok(())
+    }
+
+    /// Reports which of game/server are currently alive, reaping any that exited since
+    /// the last check.
+    pub async fn status(&self) -> ProcessStatus {
+        let mut processes = self.0.lock().await;
+        let mut status = ProcessStatus::default();
+
+        for kind in [ProcessKind::Game, ProcessKind::Server] {
+            let alive = match processes.get_mut(&kind) {
+                Some(tracked) => matches!(tracked.child.try_wait(), Ok(None)),
+                None => false,
+            };
+
+            if !alive {
+                processes.remove(&kind);
+            }
+
+            match kind {
+                ProcessKind::Game => status.game = alive,
+                ProcessKind::Server => status.server = alive,
+                ProcessKind::Custom(_) => unreachable!(),
+            }
+        }
+
+        status
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+const CONFIG_PATH: &str = "config.json";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct UpdateReport {
+    pub from_version: String,
+    pub to_version: String,
+    pub started_at: u128,
+    pub finished_at: u128,
+    pub status: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Config {
+    pub current_version: String,
+    #[serde(default)]
+    pub last_update: Option<UpdateReport>,
+}
+
+pub fn read_config() -> Result<Config, String> {
+    match std::fs::read_to_string(CONFIG_PATH) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| format!("Failed to parse config.json: {}", e)),
+        Err(_) => Ok(Config::default()),
+    }
+}
+
+pub fn write_config(config: &Config) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize config.json: {}", e))?;
+    std::fs::write(CONFIG_PATH, content).map_err(|e| format!("Failed to write config.json: {}", e))
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+fn backup_path(live: &Path) -> PathBuf {
+    let mut name = live.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Moves `live` aside to its `.bak` path (replacing any stale one left over from before the
+/// *previous* apply), then moves `staged` into `live`'s place. Both moves are plain renames,
+/// so each individual step is atomic on the same filesystem; if the second rename fails the
+/// first is undone so `live` is never left missing. The `.bak` directory is deliberately left
+/// on disk after a successful swap — it's the only thing `rollback_update` has to restore
+/// from if the new build doesn't launch — and is only cleaned up at the start of the *next*
+/// `apply_staged` call, once that update either succeeds or is rolled back.
+async fn swap_into_place(staged: &Path, live: &Path) -> Result<(), String> {
+    let backup = backup_path(live);
+
+    if fs::try_exists(&backup).await.unwrap_or(false) {
+        fs::remove_dir_all(&backup)
+            .await
+            .map_err(|e| format!("Failed to remove stale backup {}: {}", backup.display(), e))?;
+    }
+
+    let had_previous = fs::try_exists(live).await.unwrap_or(false);
+    if had_previous {
+        fs::rename(live, &backup)
+            .await
+            .map_err(|e| format!("Failed to back up {}: {}", live.display(), e))?;
+    }
+
+    if let Err(e) = fs::rename(staged, live).await {
+        if had_previous {
+            let _ = fs::rename(&backup, live).await;
+        }
+        return Err(format!("Failed to move {} into place: {}", live.display(), e));
+    }
+
+    Ok(())
+}
+
+/// Atomically swaps the freshly-extracted `staged_client`/`staged_server` directories into
+/// `client_update`/`server_update`, recording the outcome into `config.json` either way so
+/// there's always a record of what was attempted. The two swaps are made both-or-nothing:
+/// if the server swap fails after the client swap already landed, the client side is put
+/// back from its own `.bak` before returning, so a failed update never leaves the two
+/// halves on mismatched versions.
+pub async fn apply_staged(
+    to_version: &str,
+    staged_client: &Path,
+    staged_server: &Path,
+) -> Result<(), String> {
+    let started_at = now_millis();
+    let mut config = read_config()?;
+    let from_version = config.current_version.clone();
+
+    let result = async {
+        swap_into_place(staged_client, Path::new("client_update")).await?;
+        if let Err(e) = swap_into_place(staged_server, Path::new("server_update")).await {
+            let _ = restore_backup(Path::new("client_update")).await;
+            return Err(e);
+        }
+        Ok(())
+    }
+    .await;
+
+    let finished_at = now_millis();
+    config.last_update = Some(UpdateReport {
+        from_version: from_version.clone(),
+        to_version: to_version.to_string(),
+        started_at,
+        finished_at,
+        status: if result.is_ok() { "success".into() } else { "failed".into() },
+    });
+    if result.is_ok() {
+        config.current_version = to_version.to_string();
+    }
+    write_config(&config)?;
+
+    result
+}
+
+/// Restores the `.bak` directories left behind by the last successful [`apply_staged`] call
+/// and resets `current_version` to what it was before that update, for when the new build
+/// turns out not to launch. A missing backup on one side doesn't abort the other: an
+/// `apply_staged` that failed partway through (see its both-or-nothing handling above) or a
+/// repeat rollback can legitimately leave only one side with anything left to restore. Fails
+/// only if *neither* side had a backup to restore.
+pub async fn rollback() -> Result<(), String> {
+    let mut config = read_config()?;
+    let report = config
+        .last_update
+        .clone()
+        .ok_or_else(|| "No update to roll back".to_string())?;
+
+    let client_result = restore_backup(Path::new("client_update")).await;
+    let server_result = restore_backup(Path::new("server_update")).await;
+    if client_result.is_err() && server_result.is_err() {
+        return Err(format!(
+            "Nothing to roll back: {}; {}",
+            client_result.unwrap_err(),
+            server_result.unwrap_err()
+        ));
+    }
+
+    config.current_version = report.from_version.clone();
+    config.last_update = Some(UpdateReport {
+        status: "rolled_back".into(),
+        ..report
+    });
+    write_config(&config)
+}
+
+async fn restore_backup(live: &Path) -> Result<(), String> {
+    let backup = backup_path(live);
+    if !fs::try_exists(&backup).await.unwrap_or(false) {
+        return Err(format!(
+            "No backup found for {}; nothing to roll back to",
+            live.display()
+        ));
+    }
+
+    if fs::try_exists(live).await.unwrap_or(false) {
+        fs::remove_dir_all(live)
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", live.display(), e))?;
+    }
+
+    fs::rename(&backup, live)
+        .await
+        .map_err(|e| format!("Failed to restore {} from backup: {}", live.display(), e))
+}